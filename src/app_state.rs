@@ -0,0 +1,201 @@
+use crate::backend::{Backend, InputEvent, Key, MouseButton};
+use crate::universe::{bresenham_line, Universe, STAMP_CATALOG};
+
+/// A screen the `Engine` (or any other driver loop) can be running: the
+/// simulation itself, a menu, or any future addition. The active state
+/// receives every input event, advances on each frame, and draws itself;
+/// it can hand control to a different state by returning one from
+/// `next_state`.
+pub trait AppState {
+    fn handle_event(&mut self, event: &InputEvent);
+    fn update(&mut self, dt_ms: u128);
+    fn render(&mut self, backend: &mut dyn Backend);
+
+    /// Called once per frame after `update`; returning `Some` swaps the
+    /// active state for the next iteration of the loop.
+    fn next_state(&mut self) -> Option<Box<dyn AppState>> {
+        None
+    }
+}
+
+/// The Game of Life simulation screen, driven entirely through the
+/// `Backend`/`InputEvent` abstraction so every backend (SDL2, pixels,
+/// wasm) can run it as-is. Backend-specific extras, like the SDL2 egui
+/// overlay, wrap this rather than living inside it.
+pub struct GameOfLifeState {
+    universe: Universe,
+    density: f64,
+
+    mouse_dragging: bool,
+    mouse_setting: bool,
+    mouse_clearing: bool,
+    stamp_mode: bool,
+    stamp_index: usize,
+    previous_mouse_pos_x: i32,
+    previous_mouse_pos_y: i32,
+    tick_accum_ms: u128,
+}
+
+impl GameOfLifeState {
+    pub fn new(rows: u32, cols: u32) -> GameOfLifeState {
+        GameOfLifeState {
+            universe: Universe::new(rows, cols),
+            density: 0.3,
+            mouse_dragging: false,
+            mouse_setting: false,
+            mouse_clearing: false,
+            stamp_mode: false,
+            stamp_index: 0,
+            previous_mouse_pos_x: 0,
+            previous_mouse_pos_y: 0,
+            tick_accum_ms: 0,
+        }
+    }
+
+    /// Direct access to the simulation, for backend-specific wrappers
+    /// (e.g. an egui overlay) that need to read or tune it.
+    pub fn universe(&self) -> &Universe {
+        &self.universe
+    }
+
+    pub fn universe_mut(&mut self) -> &mut Universe {
+        &mut self.universe
+    }
+
+    pub fn density(&self) -> f64 {
+        self.density
+    }
+
+    pub fn set_density(&mut self, density: f64) {
+        self.density = density;
+    }
+}
+
+impl AppState for GameOfLifeState {
+    fn handle_event(&mut self, event: &InputEvent) {
+        match *event {
+            InputEvent::Quit => {}
+            InputEvent::KeyDown(key) => {
+                match key {
+                    Key::Space => self.universe.toggle_state(),
+                    Key::Right => {
+                        self.universe.run();
+                        self.universe.tick();
+                        self.universe.pause();
+                    }
+                    Key::Escape => {}
+                    Key::R => {
+                        self.universe.reset();
+                    }
+                    Key::L => {
+                        self.universe.cycle_rule();
+                    }
+                    Key::Num1 => {
+                        self.universe.randomize(0.1);
+                    }
+                    Key::Num2 => {
+                        self.universe.randomize(0.3);
+                    }
+                    Key::Num3 => {
+                        self.universe.randomize(0.5);
+                    }
+                    Key::O => {
+                        if let Ok(contents) = std::fs::read_to_string("pattern.rle") {
+                            if contents.trim_start().starts_with("#Life 1.06") {
+                                self.universe.load_life_106(&contents);
+                            } else {
+                                self.universe.load_rle(&contents);
+                            }
+                        }
+                    }
+                    Key::S => {
+                        let _ = std::fs::write("pattern.rle", self.universe.to_rle());
+                    }
+                    Key::Tab => {
+                        self.stamp_mode ^= true;
+                    }
+                    Key::Comma => {
+                        self.stamp_index = (self.stamp_index + STAMP_CATALOG.len() - 1) % STAMP_CATALOG.len();
+                    }
+                    Key::Period => {
+                        self.stamp_index = (self.stamp_index + 1) % STAMP_CATALOG.len();
+                    }
+                }
+            },
+            InputEvent::MouseButtonDown {button, x, y} => {
+                match button {
+                    MouseButton::Left if self.stamp_mode => {
+                        let (_, pattern) = STAMP_CATALOG[self.stamp_index];
+                        self.universe.stamp(x, y, pattern);
+                    },
+                    MouseButton::Left => {
+                        self.mouse_setting = true;
+                        self.universe.revive(x, y);
+                    },
+                    MouseButton::Right => {
+                        self.mouse_clearing = true;
+                        self.universe.kill(x, y);
+                    },
+                    MouseButton::Middle => self.mouse_dragging = true,
+                };
+                self.previous_mouse_pos_x = x;
+                self.previous_mouse_pos_y = y;
+            },
+            // Disable dragging, cell revive mode, and cell kill mode.
+            InputEvent::MouseButtonUp {button} => {
+                match button {
+                    MouseButton::Left => self.mouse_setting = false,
+                    MouseButton::Right => self.mouse_clearing = false,
+                    MouseButton::Middle => self.mouse_dragging = false,
+                };
+            },
+            // Scale the board with scroll wheel
+            InputEvent::MouseWheel {y} => {
+                match y {
+                    1 => self.universe.increment_scale(0.1),
+                    -1 => self.universe.increment_scale(-0.1),
+                    _ => {}
+                };
+            },
+
+            // Apply motion event like dragging, cell batch revive, cell batch kill.
+            InputEvent::MouseMotion {x, y} => {
+                if self.mouse_dragging {
+                    let x_dif = x - self.previous_mouse_pos_x;
+                    let y_dif = y - self.previous_mouse_pos_y;
+
+                    self.universe.shift(x_dif, y_dif);
+
+                    self.previous_mouse_pos_x = x;
+                    self.previous_mouse_pos_y = y;
+                } else if self.mouse_setting {
+                    let (from_x, from_y) = (self.previous_mouse_pos_x, self.previous_mouse_pos_y);
+                    bresenham_line(from_x, from_y, x, y, |px, py| {
+                        self.universe.revive(px, py);
+                    });
+                    self.previous_mouse_pos_x = x;
+                    self.previous_mouse_pos_y = y;
+                } else if self.mouse_clearing {
+                    let (from_x, from_y) = (self.previous_mouse_pos_x, self.previous_mouse_pos_y);
+                    bresenham_line(from_x, from_y, x, y, |px, py| {
+                        self.universe.kill(px, py);
+                    });
+                    self.previous_mouse_pos_x = x;
+                    self.previous_mouse_pos_y = y;
+                }
+            },
+        }
+    }
+
+    fn update(&mut self, dt_ms: u128) {
+        self.tick_accum_ms += dt_ms;
+        if self.tick_accum_ms >= self.universe.tick_interval_ms() {
+            self.universe.tick();
+            self.tick_accum_ms = 0;
+        }
+    }
+
+    fn render(&mut self, backend: &mut dyn Backend) {
+        self.universe.render(backend);
+    }
+}
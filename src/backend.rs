@@ -0,0 +1,53 @@
+use std::any::Any;
+
+/// Keys the simulation cares about, abstracted away from whichever
+/// windowing/input library a `Backend` is built on.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum Key {
+    Space,
+    Right,
+    Escape,
+    R,
+    L,
+    Num1,
+    Num2,
+    Num3,
+    O,
+    S,
+    Tab,
+    Comma,
+    Period,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+/// Platform-agnostic input event, translated from whatever the concrete
+/// `Backend` receives from its windowing library.
+pub enum InputEvent {
+    Quit,
+    KeyDown(Key),
+    MouseButtonDown { button: MouseButton, x: i32, y: i32 },
+    MouseButtonUp { button: MouseButton },
+    MouseMotion { x: i32, y: i32 },
+    MouseWheel { y: i32 },
+}
+
+/// Everything `Universe::render` and the app loop need from a concrete
+/// rendering/windowing library. The SDL2 implementation draws each cell
+/// with `fill_rect`; a pixel-buffer implementation can instead write
+/// `[r, g, b, a]` directly into its framebuffer for the same calls.
+pub trait Backend: Any {
+    fn clear(&mut self);
+    fn fill_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: (u8, u8, u8));
+    fn present(&mut self);
+    fn poll_events(&mut self) -> Vec<InputEvent>;
+
+    /// Lets callers recover a concrete backend (e.g. to reach SDL2-only
+    /// features like the egui overlay) when one is known to be in use.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
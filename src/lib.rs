@@ -0,0 +1,12 @@
+pub mod app_state;
+pub mod backend;
+pub mod universe;
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "sdl2-backend"))]
+pub mod sdl2_backend;
+
+#[cfg(feature = "pixels-backend")]
+pub mod pixels_backend;
+
+#[cfg(all(target_arch = "wasm32", feature = "pixels-backend"))]
+mod wasm;
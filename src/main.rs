@@ -1,222 +1,237 @@
-use sdl2;
-use sdl2::pixels::Color;
-use sdl2::rect::Rect;
-use sdl2::event::Event;
-use sdl2::keyboard::Keycode;
-use sdl2::mouse::MouseButton;
+#![cfg(not(target_arch = "wasm32"))]
+
+use egui_sdl2_gl::egui;
+use egui_sdl2_gl::painter::Painter;
+use egui_sdl2_gl::EguiStateHandler;
 
 use std::time::Instant;
 
-#[derive(Clone, Copy, Eq, PartialEq)]
-pub enum Cell {
-    Dead = 0,
-    Alive = 1,
+use sdl_game_of_life::app_state::{AppState, GameOfLifeState};
+use sdl_game_of_life::backend::{Backend, InputEvent, Key};
+use sdl_game_of_life::sdl2_backend::Sdl2Backend;
+use sdl_game_of_life::universe::RULE_PRESETS;
+
+/// Wraps the shared `GameOfLifeState` with the egui parameter overlay,
+/// which only makes sense on the SDL2/native desktop build. Every other
+/// backend (pixels, wasm) drives `GameOfLifeState` directly.
+pub struct DesktopGameOfLifeState {
+    simulation: GameOfLifeState,
+    pointer_over_panel: bool,
+
+    egui_ctx: egui::Context,
+    egui_state: EguiStateHandler,
+    egui_painter: Painter,
 }
 
-pub struct Universe {
-    width: u32,
-    height: u32,
-    cells: Vec<Cell>,
-    running: bool,
-    x_offset: i32,
-    y_offset: i32,
-    scale: f32,
-    // Spacing between cells in pixels
-    spacing: u32,
-    // Leg size of a cell square
-    leg_size: u32,
-
-}
-impl Universe {
-    
-    /// create a new universe populated with dead cells that is height x width big
-    /// 
-    /// # Arguments
-    /// 
-    /// * `height` - An unsigned 32 bit int representing the height of the universe
-    /// * `width` - An unsigned 32 bit int representing the width of the universe
-    /// ```
-    /// use sdl_game_of_life::Universe;
-    /// let universe = Universe::new(64, 64);
-    /// ```
-    pub fn new(height: u32, width: u32) -> Universe  {
-        let cells = vec![Cell::Dead; (width * height) as usize];
-        
-        Universe{
-            height,
-            width,
-            cells,
-            running: false,
-            x_offset: 0,
-            y_offset: 0,
-            scale: 1.0,
-            leg_size: 10,
-            spacing: 1,
+impl DesktopGameOfLifeState {
+    /// `egui_painter`/`egui_state` come from `egui_sdl2_gl::with_sdl2`,
+    /// which needs the backing window and so has to run before the
+    /// `Sdl2Backend` takes ownership of it.
+    pub fn new(rows: u32, cols: u32, egui_painter: Painter, egui_state: EguiStateHandler) -> DesktopGameOfLifeState {
+        DesktopGameOfLifeState {
+            simulation: GameOfLifeState::new(rows, cols),
+            pointer_over_panel: false,
+            egui_ctx: egui::Context::default(),
+            egui_state,
+            egui_painter,
         }
     }
 
-    fn get_index(&self, row: u32, col: u32) -> usize {
-        (row * self.width + col) as usize
-    }
-
-    /// get the number of live neighbors
-    fn get_live_neighbors(&self, row: u32, col: u32) -> u8 {
-        let mut live_count = 0;
-        
-        for row_modifier in [self.height - 1, 0, 1].iter().cloned() {
-            for col_modifier in [ self.width - 1, 0, 1].iter().cloned() {
-                if row_modifier == 0 && col_modifier == 0 {
-                    continue;
-                }
-
-                let neighbor_row = (row + row_modifier) % self.height;
-                let neighbor_col = (col + col_modifier) % self.width;
-                let index = self.get_index(neighbor_row, neighbor_col);
-                live_count += self.cells[index] as u8; // increment if alive, because alive = 1
-
-            }
+    /// Run the egui overlay directly against the SDL2 canvas. Only
+    /// available when the active `Backend` happens to be `Sdl2Backend`;
+    /// a pixel-buffer backend simply renders without the panel.
+    fn render_egui_overlay(&mut self, sdl2_backend: &mut Sdl2Backend) {
+        let raw_events = sdl2_backend.raw_events().to_vec();
+        let window = sdl2_backend.window();
+        for raw_event in raw_events {
+            self.egui_state
+                .process_input(window, raw_event, &mut self.egui_painter);
         }
 
-        live_count
-    } 
-
-    /// Moves the state of the game by one tick
-    pub fn tick(& mut self) {
-
-        match self.running {
-            false => return,
-            true => {
-                let mut next = self.cells.clone();
-        
-                for row in 0..self.height {
-                    for col in 0..self.width {
-                        let index = self.get_index(row, col);
-                        let live_neighbors = self.get_live_neighbors(row, col);
-        
-                        next[index] = match (live_neighbors, self.cells[index]){
-                            // if neighbors are less than two, then cell dies
-                            (x, Cell::Alive) if x < 2 => Cell::Dead,
-                            // if neighbors more than tree, then cell dies
-                            (x, Cell::Alive) if x > 3 => Cell::Dead,
-                            // if neighbors 2 or 3, then cell stays alive
-                            (2, Cell::Alive) | (3, Cell::Alive) => Cell::Alive,
-                            // if neighbors exactly 3, then revive
-                            (3, Cell::Dead) => Cell::Alive,
-                            // stay the same for other states
-                            (_, otherwise) => otherwise,
-                        };
-                    }
-                }
-                
-                self.cells = next;
-            }
+        let mut density = self.simulation.density();
+        let universe = self.simulation.universe_mut();
+        let mut leg_size = universe.leg_size();
+        let mut spacing = universe.spacing();
+        let mut scale = universe.scale();
+        let mut tick_interval_ms = universe.tick_interval_ms() as f32;
+        let mut selected_rule = universe.rule_index();
+        let mut start_clicked = false;
+        let mut pause_clicked = false;
+        let mut step_clicked = false;
+        let mut randomize_clicked = false;
+
+        let raw_input = std::mem::take(&mut self.egui_state.input);
+        let mut pointer_over_panel = false;
+        let full_output = self.egui_ctx.run(raw_input, |ctx| {
+            egui::Window::new("Game of Life").show(ctx, |ui| {
+                ui.add(egui::Slider::new(&mut leg_size, 2..=40).text("cell size"));
+                ui.add(egui::Slider::new(&mut spacing, 0..=10).text("spacing"));
+                ui.add(egui::Slider::new(&mut scale, 0.1..=4.0).text("scale"));
+                ui.add(egui::Slider::new(&mut tick_interval_ms, 10.0..=1000.0).text("tick ms"));
+                ui.add(egui::Slider::new(&mut density, 0.0..=1.0).text("density"));
+
+                ui.horizontal(|ui| {
+                    start_clicked = ui.button("Start").clicked();
+                    pause_clicked = ui.button("Pause").clicked();
+                    step_clicked = ui.button("Step").clicked();
+                });
+
+                egui::ComboBox::from_label("rule")
+                    .selected_text(RULE_PRESETS[selected_rule].0)
+                    .show_ui(ui, |ui| {
+                        for (index, (name, _)) in RULE_PRESETS.iter().enumerate() {
+                            ui.selectable_value(&mut selected_rule, index, *name);
+                        }
+                    });
+
+                randomize_clicked = ui.button("Randomize").clicked();
+            });
+
+            pointer_over_panel = ctx.wants_pointer_input();
+        });
+        self.egui_state
+            .process_output(sdl2_backend.window(), &full_output.platform_output);
+        self.pointer_over_panel = pointer_over_panel;
+
+        // `egui_ctx.run` only builds the scene description; it still has to
+        // be tessellated into triangles and handed to the GL painter before
+        // anything shows up on screen.
+        let paint_jobs = self.egui_ctx.tessellate(full_output.shapes);
+        self.egui_painter
+            .paint_jobs(None, full_output.textures_delta, paint_jobs);
+
+        self.simulation.set_density(density);
+        let universe = self.simulation.universe_mut();
+        universe.set_leg_size(leg_size);
+        universe.set_spacing(spacing);
+        universe.set_scale(scale);
+        universe.set_tick_interval(tick_interval_ms as u128);
+        if selected_rule != universe.rule_index() {
+            universe.set_rule_by_index(selected_rule);
         }
 
-    }
-
-    pub fn render(&self, canvas: &mut sdl2::render::Canvas<sdl2::video::Window>) {
-
-        let mut current_y = 0 + self.y_offset;
-        // currently the size of the cell is 10x10 pixels with 2 pixel border
-        for row in self.cells.as_slice().chunks(self.width as usize) {
-            let mut current_x = 0 + self.x_offset;
-            for &cell in row {
-                if cell == Cell::Alive {
-                    canvas.set_draw_color(Color::RGB(255, 255, 255));
-                } else {
-                    canvas.set_draw_color(Color::RGB(0, 0, 0));
-                }
-                let leg_size = (self.leg_size as f32 * self.scale).floor() as u32;
-                
-                canvas.fill_rect(Rect::new(current_x, current_y, leg_size, leg_size)).unwrap();
-
-
-                current_x += ((self.leg_size + self.spacing * 2) as f32 * self.scale) as i32;
-            }
-
-            current_y += ((self.leg_size + self.spacing * 2) as f32 * self.scale) as i32;
+        if start_clicked {
+            universe.run();
+        }
+        if pause_clicked {
+            universe.pause();
+        }
+        if step_clicked {
+            universe.run();
+            universe.tick();
+            universe.pause();
+        }
+        if randomize_clicked {
+            universe.randomize(density);
         }
     }
-    
-    pub fn toggle_state(&mut self) {
-        self.running ^= true;
-    }
+}
 
-    pub fn pause(&mut self) {
-        self.running = false;
+impl AppState for DesktopGameOfLifeState {
+    fn handle_event(&mut self, event: &InputEvent) {
+        // Mouse presses and motion are swallowed while the pointer is over
+        // the egui panel, so dragging a slider doesn't also paint cells
+        // underneath. Button releases and wheel scrolls still pass through.
+        match event {
+            InputEvent::MouseButtonDown { .. } | InputEvent::MouseMotion { .. } if self.pointer_over_panel => {}
+            _ => self.simulation.handle_event(event),
+        }
     }
 
-    pub fn run(&mut self) {
-        self.running = true;
+    fn update(&mut self, dt_ms: u128) {
+        self.simulation.update(dt_ms);
     }
 
-    pub fn shift(&mut self, x: i32, y: i32) {
-        self.x_offset += x;
-        self.y_offset += y;
-    }
+    fn render(&mut self, backend: &mut dyn Backend) {
+        self.simulation.render(backend);
 
-    fn get_by_coordinates(&self, x: i32, y: i32) -> Option<usize> {
-        // TODO use dynamic cell size to get coordinates when scaling
-        let x_size = ((self.leg_size + self.spacing * 2) as f32 * self.scale) as i32;
-        let y_size = ((self.leg_size + self.spacing * 2) as f32 * self.scale) as i32;
-        // Take the cell size and spacing, multiply by it's index
-        
-        let y_index = (((y - self.y_offset) as f32) / (y_size as f32)).floor();
-        let x_index = (((x - self.x_offset) as f32) / (x_size as f32)).floor();
-
-        if y_index < 0.0 || x_index < 0.0 || y_index >= self.height as f32 || x_index >= self.width as f32 {
-            return None;
+        if let Some(sdl2_backend) = backend.as_any_mut().downcast_mut::<Sdl2Backend>() {
+            self.render_egui_overlay(sdl2_backend);
         }
+    }
+}
 
-        Some((y_index as u32 * self.width + x_index as u32) as usize)
+/// Builds a configured `Engine` instead of hard-coding window/grid
+/// constants. Defaults match the previous fixed behavior: a fullscreen
+/// 1000x1000 window titled "SDL Game of Life" running a 64x115 universe.
+pub struct AppBuilder {
+    width: u32,
+    height: u32,
+    title: String,
+    fullscreen: bool,
+    rows: u32,
+    cols: u32,
+}
 
+impl Default for AppBuilder {
+    fn default() -> AppBuilder {
+        AppBuilder::new()
     }
+}
 
-    pub fn kill(&mut self, x: i32, y: i32) {
-        let cell_index = match self.get_by_coordinates(x, y) {
-            Some(index) => index,
-            None => return
-        };
-        self.cells[cell_index] = Cell::Dead;
+impl AppBuilder {
+    pub fn new() -> AppBuilder {
+        AppBuilder {
+            width: 1000,
+            height: 1000,
+            title: "SDL Game of Life".to_string(),
+            fullscreen: true,
+            rows: 64,
+            cols: 115,
+        }
     }
 
-    pub fn revive(&mut self, x: i32, y: i32) {
-        let cell_index = match self.get_by_coordinates(x, y) {
-            Some(index) => index,
-            None => return
-        };
-        self.cells[cell_index] = Cell::Alive
+    pub fn with_resolution(mut self, width: u32, height: u32) -> AppBuilder {
+        self.width = width;
+        self.height = height;
+        self
     }
 
-    pub fn increment_scale(&mut self, increment: f32) {
-        self.scale += increment;
+    pub fn with_title(mut self, title: &str) -> AppBuilder {
+        self.title = title.to_string();
+        self
     }
 
-    pub fn reset(&mut self) {
-        self.cells = vec![Cell::Dead; (self.width * self.height) as usize];
+    pub fn with_fullscreen(mut self, fullscreen: bool) -> AppBuilder {
+        self.fullscreen = fullscreen;
+        self
     }
-}
-pub struct Engine {
-    universe: Universe,
-    canvas: sdl2::render::Canvas<sdl2::video::Window>,
-    event_pump: sdl2::EventPump,
-}
 
-impl Engine {
-    pub fn new() -> Result<Engine, String> {
-        let universe = Universe::new(64, 115);
+    pub fn with_grid(mut self, rows: u32, cols: u32) -> AppBuilder {
+        self.rows = rows;
+        self.cols = cols;
+        self
+    }
 
+    pub fn build(self) -> Result<Engine, String> {
         let sdl_context = sdl2::init()?;
         let video_subsystem = sdl_context.video()?;
 
-        let mut window = match video_subsystem.window("SDL Game of Life", 1000, 1000)
+        let gl_attr = video_subsystem.gl_attr();
+        gl_attr.set_context_profile(sdl2::video::GLProfile::Core);
+        gl_attr.set_context_version(3, 2);
+
+        let mut window = match video_subsystem.window(&self.title, self.width, self.height)
                 .position_centered()
+                .opengl()
                 .build() {
             Ok(sub_system) => sub_system,
             Err(e) => return Err(format!("Could not build window: {:?}", e))
         };
 
-        window.set_fullscreen(sdl2::video::FullscreenType::Desktop)?;
+        if self.fullscreen {
+            window.set_fullscreen(sdl2::video::FullscreenType::Desktop)?;
+        }
+
+        let gl_context = window.gl_create_context()
+            .map_err(|e| format!("Could not create GL context: {:?}", e))?;
+        egui_sdl2_gl::gl::load_with(|name| video_subsystem.gl_get_proc_address(name) as *const _);
+
+        let (egui_painter, egui_state) = egui_sdl2_gl::with_sdl2(
+            &window,
+            egui_sdl2_gl::ShaderVersion::Default,
+            egui_sdl2_gl::DpiScaling::Default,
+        );
 
         let canvas = match window.into_canvas().build() {
             Ok(canvas) => canvas,
@@ -225,129 +240,61 @@ impl Engine {
 
         let event_pump = sdl_context.event_pump().unwrap();
 
+        let backend: Box<dyn Backend> = Box::new(Sdl2Backend::new(canvas, event_pump, gl_context));
+        let state: Box<dyn AppState> =
+            Box::new(DesktopGameOfLifeState::new(self.rows, self.cols, egui_painter, egui_state));
+
         Ok(Engine {
-            canvas,
-            universe,
-            event_pump
+            backend,
+            state,
         })
     }
+}
 
-    // Starts the game loop
-    pub fn run(&mut self) {
-        self.canvas.set_draw_color(Color::RGB(120, 120, 120));
-        self.canvas.clear();
-        self.canvas.present();
-
-        let mut mouse_dragging = false;
-        let mut mouse_setting = false;
-        let mut mouse_clearing = false;
+pub struct Engine {
+    backend: Box<dyn Backend>,
+    state: Box<dyn AppState>,
+}
 
-        let mut previous_mouse_pos_x: i32 = 0;
-        let mut previous_mouse_pos_y: i32 = 0;
+impl Engine {
+    // Starts the game loop, dispatching events, updates, and rendering to
+    // whichever `AppState` is currently active.
+    pub fn run(&mut self) {
+        self.backend.clear();
+        self.backend.present();
 
         let mut render_timer = Instant::now();
-        let mut tick_timer = Instant::now();
+        let mut frame_timer = Instant::now();
 
         'running: loop {
-            self.canvas.set_draw_color(Color::RGB(120, 120, 120));
-            self.canvas.clear();
+            self.backend.clear();
 
-            for event in self.event_pump.poll_iter() {
+            for event in self.backend.poll_events() {
                 match event {
-                    Event::Quit {..} => {
-                        break 'running
-                    }
-                    Event::KeyDown {keycode, ..} => {
-                        match keycode {
-                            Some(Keycode::Space) => self.universe.toggle_state(),
-                            Some(Keycode::Right) => {
-                                self.universe.run();
-                                self.universe.tick();
-                                self.universe.pause();
-                            }
-                            Some(Keycode::Escape) => {
-                                break 'running
-                            }
-                            Some(Keycode::R) => {
-                                self.universe.reset();
-                            }
-                            _ => {}
-                        }
-                    },
-                    // Enable dragging, cell revive mode, and cell kill mode.
-                    Event::MouseButtonDown {mouse_btn, x, y, ..} => {
-                        match mouse_btn {
-                            MouseButton::Left => {
-                                mouse_setting = true;
-                                self.universe.revive(x, y);
-
-                            },
-                            MouseButton::Right => {
-                                mouse_clearing = true;
-                                self.universe.kill(x, y);
-                            },
-                            MouseButton::Middle => mouse_dragging = true,
-                            _ => {}
-                        };
-                        previous_mouse_pos_x = x;
-                        previous_mouse_pos_y = y;
-                    },
-                    // Disable dragging, cell revive mode, and cell kill mode.
-                    Event::MouseButtonUp {mouse_btn, ..} => {
-                        match mouse_btn {
-                            MouseButton::Left => mouse_setting = false,
-                            MouseButton::Right => mouse_clearing = false,
-                            MouseButton::Middle => mouse_dragging = false,
-                            _ => {}
-                        };
-                    },
-                    // Scale the board with scroll wheel
-                    Event::MouseWheel {y, ..} => {
-                        match y {
-                            1 => self.universe.increment_scale(0.1),
-                            -1 => self.universe.increment_scale(-0.1),
-                            _ => {}
-                        };
-                    },
-
-                    // Apply motion event like dragging, cell batch revive, cell batch kill.
-                    Event::MouseMotion {x, y, ..} => {
-                        if mouse_dragging {
-                            let x_dif = x - previous_mouse_pos_x;
-                            let y_dif = y - previous_mouse_pos_y;
-    
-                            self.universe.shift(x_dif, y_dif);
-
-                            previous_mouse_pos_x = x;
-                            previous_mouse_pos_y = y;
-                        } else if mouse_setting {
-                            self.universe.revive(x, y);
-                        } else if mouse_clearing {
-                            self.universe.kill(x, y);
-                        }
-                    },
-                    _ => {
-                    }
+                    InputEvent::Quit | InputEvent::KeyDown(Key::Escape) => break 'running,
+                    event => self.state.handle_event(&event),
                 }
             }
 
-            if tick_timer.elapsed().as_millis() >= 100 {
-                self.universe.tick();
-                tick_timer = Instant::now();
+            let dt_ms = frame_timer.elapsed().as_millis();
+            frame_timer = Instant::now();
+            self.state.update(dt_ms);
+
+            if let Some(next_state) = self.state.next_state() {
+                self.state = next_state;
             }
 
-            if render_timer.elapsed().as_millis() >= 8  {
-                self.universe.render(&mut self.canvas);
-                self.canvas.present();
+            if render_timer.elapsed().as_millis() >= 8 {
+                self.state.render(self.backend.as_mut());
+                self.backend.present();
                 render_timer = Instant::now();
             }
-            
         }
     }
 }
 
 fn main() {
-    let mut engine = match Engine::new() {
+    let mut engine = match AppBuilder::new().build() {
         Ok(engine) => engine,
         Err(error) => panic!("Engine Failed: {:?}", error)
     };
@@ -0,0 +1,73 @@
+use std::any::Any;
+
+use pixels::Pixels;
+
+use crate::backend::{Backend, InputEvent};
+
+/// A `Backend` that draws straight into a `pixels`/`winit` framebuffer
+/// instead of going through SDL2's `fill_rect`, so the simulation can run
+/// outside of a native SDL2 window (notably in a `wasm32` build).
+///
+/// Unlike [`crate::sdl2_backend::Sdl2Backend`], which owns the window's
+/// event loop, `winit`'s loop drives the program rather than the other
+/// way around. Events are handed to this backend via [`push_event`] from
+/// inside that loop and drained on the next [`Backend::poll_events`] call.
+pub struct PixelsBackend {
+    pixels: Pixels,
+    buffer_width: u32,
+    queued_events: Vec<InputEvent>,
+}
+
+impl PixelsBackend {
+    pub fn new(pixels: Pixels, buffer_width: u32) -> PixelsBackend {
+        PixelsBackend {
+            pixels,
+            buffer_width,
+            queued_events: Vec::new(),
+        }
+    }
+
+    /// Feed an event observed by the owning `winit` event loop.
+    pub fn push_event(&mut self, event: InputEvent) {
+        self.queued_events.push(event);
+    }
+}
+
+impl Backend for PixelsBackend {
+    fn clear(&mut self) {
+        for pixel in self.pixels.frame_mut().chunks_exact_mut(4) {
+            pixel.copy_from_slice(&[120, 120, 120, 255]);
+        }
+    }
+
+    fn fill_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: (u8, u8, u8)) {
+        let buffer_width = self.buffer_width;
+        let frame = self.pixels.frame_mut();
+        let buffer_height = (frame.len() / 4) as u32 / buffer_width.max(1);
+
+        for row in y..(y + height as i32) {
+            if row < 0 || row as u32 >= buffer_height {
+                continue;
+            }
+            for col in x..(x + width as i32) {
+                if col < 0 || col as u32 >= buffer_width {
+                    continue;
+                }
+                let index = ((row as u32 * buffer_width + col as u32) * 4) as usize;
+                frame[index..index + 4].copy_from_slice(&[color.0, color.1, color.2, 255]);
+            }
+        }
+    }
+
+    fn present(&mut self) {
+        let _ = self.pixels.render();
+    }
+
+    fn poll_events(&mut self) -> Vec<InputEvent> {
+        std::mem::take(&mut self.queued_events)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,133 @@
+use std::any::Any;
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::mouse::MouseButton as Sdl2MouseButton;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+
+use crate::backend::{Backend, InputEvent, Key, MouseButton};
+
+/// The default `Backend`: renders cells with SDL2's `fill_rect` and
+/// translates SDL2 events into the crate's platform-agnostic `InputEvent`.
+pub struct Sdl2Backend {
+    canvas: sdl2::render::Canvas<sdl2::video::Window>,
+    event_pump: sdl2::EventPump,
+    last_raw_events: Vec<Event>,
+    // Kept alive for as long as the backend is: dropping it destroys the
+    // GL context the egui overlay paints into.
+    gl_context: sdl2::video::GLContext,
+}
+
+impl Sdl2Backend {
+    pub fn new(
+        canvas: sdl2::render::Canvas<sdl2::video::Window>,
+        event_pump: sdl2::EventPump,
+        gl_context: sdl2::video::GLContext,
+    ) -> Sdl2Backend {
+        Sdl2Backend {
+            canvas,
+            event_pump,
+            last_raw_events: Vec::new(),
+            gl_context,
+        }
+    }
+
+    /// Raw access to the SDL2 canvas, for SDL2-only features (like the
+    /// egui overlay) that a generic `Backend` can't express.
+    pub fn canvas_mut(&mut self) -> &mut sdl2::render::Canvas<sdl2::video::Window> {
+        &mut self.canvas
+    }
+
+    /// The window backing the canvas, for SDL2-only features (like the
+    /// egui overlay) that need it directly rather than through the canvas.
+    pub fn window(&self) -> &sdl2::video::Window {
+        self.canvas.window()
+    }
+
+    /// The raw SDL2 events seen during the most recent `poll_events` call,
+    /// for consumers (like egui) that need more than `InputEvent` carries.
+    pub fn raw_events(&self) -> &[Event] {
+        &self.last_raw_events
+    }
+
+    /// The GL context backing the egui overlay's painter.
+    pub fn gl_context(&self) -> &sdl2::video::GLContext {
+        &self.gl_context
+    }
+}
+
+impl Backend for Sdl2Backend {
+    fn clear(&mut self) {
+        self.canvas.set_draw_color(Color::RGB(120, 120, 120));
+        self.canvas.clear();
+    }
+
+    fn fill_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: (u8, u8, u8)) {
+        self.canvas.set_draw_color(Color::RGB(color.0, color.1, color.2));
+        let _ = self.canvas.fill_rect(Rect::new(x, y, width, height));
+    }
+
+    // The canvas and the egui overlay's painter share the same GL context,
+    // so a single `gl_swap_window` flips both to the screen; `canvas.present()`
+    // would only flush the SDL2 renderer's own draws and leave the overlay
+    // painted into a buffer that's never shown.
+    fn present(&mut self) {
+        self.canvas.window().gl_swap_window();
+    }
+
+    fn poll_events(&mut self) -> Vec<InputEvent> {
+        let raw: Vec<Event> = self.event_pump.poll_iter().collect();
+        let translated = raw.iter().filter_map(translate_event).collect();
+        self.last_raw_events = raw;
+        translated
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+fn translate_event(event: &Event) -> Option<InputEvent> {
+    match *event {
+        Event::Quit {..} => Some(InputEvent::Quit),
+        Event::KeyDown {keycode: Some(keycode), ..} => translate_keycode(keycode).map(InputEvent::KeyDown),
+        Event::MouseButtonDown {mouse_btn, x, y, ..} => {
+            translate_mouse_button(mouse_btn).map(|button| InputEvent::MouseButtonDown { button, x, y })
+        }
+        Event::MouseButtonUp {mouse_btn, ..} => {
+            translate_mouse_button(mouse_btn).map(|button| InputEvent::MouseButtonUp { button })
+        }
+        Event::MouseWheel {y, ..} => Some(InputEvent::MouseWheel { y }),
+        Event::MouseMotion {x, y, ..} => Some(InputEvent::MouseMotion { x, y }),
+        _ => None,
+    }
+}
+
+fn translate_keycode(keycode: Keycode) -> Option<Key> {
+    match keycode {
+        Keycode::Space => Some(Key::Space),
+        Keycode::Right => Some(Key::Right),
+        Keycode::Escape => Some(Key::Escape),
+        Keycode::R => Some(Key::R),
+        Keycode::L => Some(Key::L),
+        Keycode::Num1 => Some(Key::Num1),
+        Keycode::Num2 => Some(Key::Num2),
+        Keycode::Num3 => Some(Key::Num3),
+        Keycode::O => Some(Key::O),
+        Keycode::S => Some(Key::S),
+        Keycode::Tab => Some(Key::Tab),
+        Keycode::Comma => Some(Key::Comma),
+        Keycode::Period => Some(Key::Period),
+        _ => None,
+    }
+}
+
+fn translate_mouse_button(mouse_btn: Sdl2MouseButton) -> Option<MouseButton> {
+    match mouse_btn {
+        Sdl2MouseButton::Left => Some(MouseButton::Left),
+        Sdl2MouseButton::Right => Some(MouseButton::Right),
+        Sdl2MouseButton::Middle => Some(MouseButton::Middle),
+        _ => None,
+    }
+}
@@ -0,0 +1,693 @@
+use crate::backend::Backend;
+use rand::Rng;
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum Cell {
+    Dead = 0,
+    Alive = 1,
+}
+
+/// A cellular automaton ruleset in B/S ("birth/survival") notation, e.g.
+/// `B3/S23` for Conway's Life or `B36/S23` for HighLife.
+///
+/// `birth` and `survival` are bitmasks where bit `n` set means "a cell with
+/// `n` live neighbors is born" / "...survives" respectively.
+#[derive(Clone, Copy)]
+pub struct Rule {
+    birth: u16,
+    survival: u16,
+}
+
+impl Rule {
+    /// Parse a rule string of the form `B<digits>/S<digits>`, e.g. `B3/S23`.
+    ///
+    /// Panics if the string isn't in that shape; callers only ever pass in
+    /// the built-in rule catalog below.
+    pub fn parse(rule: &str) -> Rule {
+        let mut birth: u16 = 0;
+        let mut survival: u16 = 0;
+
+        for half in rule.split('/') {
+            let mut chars = half.chars();
+            match chars.next() {
+                Some('B') | Some('b') => {
+                    for digit in chars {
+                        birth |= 1 << digit.to_digit(10).expect("invalid digit in B/S rule");
+                    }
+                }
+                Some('S') | Some('s') => {
+                    for digit in chars {
+                        survival |= 1 << digit.to_digit(10).expect("invalid digit in B/S rule");
+                    }
+                }
+                _ => panic!("invalid B/S rule string: {}", rule),
+            }
+        }
+
+        Rule { birth, survival }
+    }
+
+    fn is_born(&self, live_neighbors: u8) -> bool {
+        self.birth & (1 << live_neighbors) != 0
+    }
+
+    fn survives(&self, live_neighbors: u8) -> bool {
+        self.survival & (1 << live_neighbors) != 0
+    }
+}
+
+impl Default for Rule {
+    /// Conway's original rule: B3/S23.
+    fn default() -> Rule {
+        Rule::parse("B3/S23")
+    }
+}
+
+/// Named rules a user can cycle through without recompiling.
+pub const RULE_PRESETS: &[(&str, &str)] = &[
+    ("Conway's Life", "B3/S23"),
+    ("HighLife", "B36/S23"),
+    ("Day & Night", "B3678/S34678"),
+];
+
+pub struct Universe {
+    width: u32,
+    height: u32,
+    cells: Vec<Cell>,
+    running: bool,
+    x_offset: i32,
+    y_offset: i32,
+    scale: f32,
+    // Spacing between cells in pixels
+    spacing: u32,
+    // Leg size of a cell square
+    leg_size: u32,
+    rule: Rule,
+    rule_index: usize,
+    tick_interval_ms: u128,
+
+}
+impl Universe {
+
+    /// create a new universe populated with dead cells that is height x width big
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - An unsigned 32 bit int representing the height of the universe
+    /// * `width` - An unsigned 32 bit int representing the width of the universe
+    /// ```
+    /// use sdl_game_of_life::universe::Universe;
+    /// let universe = Universe::new(64, 64);
+    /// ```
+    pub fn new(height: u32, width: u32) -> Universe  {
+        let cells = vec![Cell::Dead; (width * height) as usize];
+
+        Universe{
+            height,
+            width,
+            cells,
+            running: false,
+            x_offset: 0,
+            y_offset: 0,
+            scale: 1.0,
+            leg_size: 10,
+            spacing: 1,
+            rule: Rule::default(),
+            rule_index: 0,
+            tick_interval_ms: 100,
+        }
+    }
+
+    pub fn set_leg_size(&mut self, leg_size: u32) {
+        self.leg_size = leg_size;
+    }
+
+    pub fn set_spacing(&mut self, spacing: u32) {
+        self.spacing = spacing;
+    }
+
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    pub fn set_tick_interval(&mut self, tick_interval_ms: u128) {
+        self.tick_interval_ms = tick_interval_ms;
+    }
+
+    pub fn tick_interval_ms(&self) -> u128 {
+        self.tick_interval_ms
+    }
+
+    pub fn leg_size(&self) -> u32 {
+        self.leg_size
+    }
+
+    pub fn spacing(&self) -> u32 {
+        self.spacing
+    }
+
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Set the active ruleset directly.
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+
+    /// Cycle to the next named rule in [`RULE_PRESETS`], wrapping around.
+    pub fn cycle_rule(&mut self) {
+        self.set_rule_by_index((self.rule_index + 1) % RULE_PRESETS.len());
+    }
+
+    /// Select a named rule from [`RULE_PRESETS`] by index.
+    pub fn set_rule_by_index(&mut self, index: usize) {
+        self.rule_index = index;
+        let (_, rule_str) = RULE_PRESETS[index];
+        self.rule = Rule::parse(rule_str);
+    }
+
+    pub fn rule_index(&self) -> usize {
+        self.rule_index
+    }
+
+    fn get_index(&self, row: u32, col: u32) -> usize {
+        (row * self.width + col) as usize
+    }
+
+    /// get the number of live neighbors
+    fn get_live_neighbors(&self, row: u32, col: u32) -> u8 {
+        let mut live_count = 0;
+
+        for row_modifier in [self.height - 1, 0, 1].iter().cloned() {
+            for col_modifier in [ self.width - 1, 0, 1].iter().cloned() {
+                if row_modifier == 0 && col_modifier == 0 {
+                    continue;
+                }
+
+                let neighbor_row = (row + row_modifier) % self.height;
+                let neighbor_col = (col + col_modifier) % self.width;
+                let index = self.get_index(neighbor_row, neighbor_col);
+                live_count += self.cells[index] as u8; // increment if alive, because alive = 1
+
+            }
+        }
+
+        live_count
+    }
+
+    /// Moves the state of the game by one tick
+    pub fn tick(& mut self) {
+
+        match self.running {
+            false => (),
+            true => {
+                let mut next = self.cells.clone();
+
+                for row in 0..self.height {
+                    for col in 0..self.width {
+                        let index = self.get_index(row, col);
+                        let live_neighbors = self.get_live_neighbors(row, col);
+
+                        next[index] = match self.cells[index] {
+                            Cell::Alive if self.rule.survives(live_neighbors) => Cell::Alive,
+                            Cell::Alive => Cell::Dead,
+                            Cell::Dead if self.rule.is_born(live_neighbors) => Cell::Alive,
+                            otherwise => otherwise,
+                        };
+                    }
+                }
+
+                self.cells = next;
+            }
+        }
+
+    }
+
+    /// Draw every cell through a [`Backend`], so the simulation doesn't
+    /// need to know whether it's painting to an SDL2 canvas or a raw
+    /// pixel buffer.
+    pub fn render(&self, backend: &mut dyn Backend) {
+
+        let mut current_y = self.y_offset;
+        // currently the size of the cell is 10x10 pixels with 2 pixel border
+        for row in self.cells.as_slice().chunks(self.width as usize) {
+            let mut current_x = self.x_offset;
+            for &cell in row {
+                let color = if cell == Cell::Alive {
+                    (255, 255, 255)
+                } else {
+                    (0, 0, 0)
+                };
+                let leg_size = (self.leg_size as f32 * self.scale).floor() as u32;
+
+                backend.fill_rect(current_x, current_y, leg_size, leg_size, color);
+
+                current_x += ((self.leg_size + self.spacing * 2) as f32 * self.scale) as i32;
+            }
+
+            current_y += ((self.leg_size + self.spacing * 2) as f32 * self.scale) as i32;
+        }
+    }
+
+    pub fn toggle_state(&mut self) {
+        self.running ^= true;
+    }
+
+    pub fn pause(&mut self) {
+        self.running = false;
+    }
+
+    pub fn run(&mut self) {
+        self.running = true;
+    }
+
+    pub fn shift(&mut self, x: i32, y: i32) {
+        self.x_offset += x;
+        self.y_offset += y;
+    }
+
+    fn get_by_coordinates(&self, x: i32, y: i32) -> Option<usize> {
+        // TODO use dynamic cell size to get coordinates when scaling
+        let x_size = ((self.leg_size + self.spacing * 2) as f32 * self.scale) as i32;
+        let y_size = ((self.leg_size + self.spacing * 2) as f32 * self.scale) as i32;
+        // Take the cell size and spacing, multiply by it's index
+
+        let y_index = (((y - self.y_offset) as f32) / (y_size as f32)).floor();
+        let x_index = (((x - self.x_offset) as f32) / (x_size as f32)).floor();
+
+        if y_index < 0.0 || x_index < 0.0 || y_index >= self.height as f32 || x_index >= self.width as f32 {
+            return None;
+        }
+
+        Some((y_index as u32 * self.width + x_index as u32) as usize)
+
+    }
+
+    pub fn kill(&mut self, x: i32, y: i32) {
+        let cell_index = match self.get_by_coordinates(x, y) {
+            Some(index) => index,
+            None => return
+        };
+        self.cells[cell_index] = Cell::Dead;
+    }
+
+    pub fn revive(&mut self, x: i32, y: i32) {
+        let cell_index = match self.get_by_coordinates(x, y) {
+            Some(index) => index,
+            None => return
+        };
+        self.cells[cell_index] = Cell::Alive
+    }
+
+    /// Paste a stamp pattern (relative live-cell offsets) anchored at the
+    /// clicked screen position, with its top-left corner at that cell.
+    /// Offsets that land off-grid are ignored, matching
+    /// [`Universe::get_by_coordinates`]'s bounds behavior.
+    pub fn stamp(&mut self, x: i32, y: i32, pattern: &[(i32, i32)]) {
+        let anchor_index = match self.get_by_coordinates(x, y) {
+            Some(index) => index,
+            None => return,
+        };
+        let anchor_row = (anchor_index as u32) / self.width;
+        let anchor_col = (anchor_index as u32) % self.width;
+
+        for &(dx, dy) in pattern {
+            let row = anchor_row as i32 + dy;
+            let col = anchor_col as i32 + dx;
+            if row < 0 || col < 0 || row >= self.height as i32 || col >= self.width as i32 {
+                continue;
+            }
+            let index = self.get_index(row as u32, col as u32);
+            self.cells[index] = Cell::Alive;
+        }
+    }
+
+    pub fn increment_scale(&mut self, increment: f32) {
+        self.scale += increment;
+    }
+
+    pub fn reset(&mut self) {
+        self.cells = vec![Cell::Dead; (self.width * self.height) as usize];
+    }
+
+    /// Fill the universe with noise: each cell is independently alive with
+    /// probability `density` (a value in `[0, 1]`).
+    pub fn randomize(&mut self, density: f64) {
+        let mut rng = rand::thread_rng();
+        for cell in self.cells.iter_mut() {
+            *cell = if rng.gen::<f64>() < density {
+                Cell::Alive
+            } else {
+                Cell::Dead
+            };
+        }
+    }
+
+    /// Blit a pattern, given as relative live-cell offsets from its
+    /// top-left corner, into the universe centered at its current
+    /// dimensions, clearing the board first.
+    fn blit_centered(&mut self, pattern_width: u32, pattern_height: u32, live_cells: &[(u32, u32)]) {
+        self.cells = vec![Cell::Dead; (self.width * self.height) as usize];
+
+        let row_offset = (self.height.saturating_sub(pattern_height)) / 2;
+        let col_offset = (self.width.saturating_sub(pattern_width)) / 2;
+
+        for &(col, row) in live_cells {
+            let row = row + row_offset;
+            let col = col + col_offset;
+            if row < self.height && col < self.width {
+                let index = self.get_index(row, col);
+                self.cells[index] = Cell::Alive;
+            }
+        }
+    }
+
+    /// Load a pattern encoded in the RLE format (as used by LifeWiki), e.g.
+    /// a glider or a Gosper glider gun, centering it in the universe.
+    ///
+    /// Expects a `x = <w>, y = <h>` header line (rule info, if present, is
+    /// ignored) followed by a run-length-encoded body: `<count>b` for dead
+    /// cells, `<count>o` for live cells, `$` to end a row, `!` to terminate
+    /// the pattern. A missing count defaults to 1.
+    pub fn load_rle(&mut self, rle: &str) {
+        let mut pattern_width = 0u32;
+        let mut pattern_height = 0u32;
+        let mut body_lines = Vec::new();
+
+        for line in rle.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('x') {
+                for part in line.split(',') {
+                    let mut kv = part.splitn(2, '=');
+                    let key = kv.next().unwrap_or("").trim();
+                    let value = kv.next().unwrap_or("").trim();
+                    match key {
+                        "x" => pattern_width = value.parse().unwrap_or(0),
+                        "y" => pattern_height = value.parse().unwrap_or(0),
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+            body_lines.push(line);
+        }
+
+        let body: String = body_lines.join("");
+
+        // No real RLE pattern needs a run anywhere near this long; capping
+        // here keeps a malformed file's digit run from overflowing `count`
+        // or blowing up the `'o'` loop below into an effectively infinite one.
+        const MAX_RUN_LENGTH: u32 = 100_000;
+
+        let mut live_cells = Vec::new();
+        let mut count: u32 = 0;
+        let mut row: u32 = 0;
+        let mut col: u32 = 0;
+
+        for ch in body.chars() {
+            match ch {
+                '0'..='9' => {
+                    count = (count.saturating_mul(10) + ch.to_digit(10).unwrap()).min(MAX_RUN_LENGTH);
+                }
+                'b' => {
+                    col += count.max(1);
+                    count = 0;
+                }
+                'o' => {
+                    let run = count.max(1);
+                    for _ in 0..run {
+                        live_cells.push((col, row));
+                        col += 1;
+                    }
+                    count = 0;
+                }
+                '$' => {
+                    row += count.max(1);
+                    col = 0;
+                    count = 0;
+                }
+                '!' => break,
+                _ => {}
+            }
+        }
+
+        self.blit_centered(pattern_width, pattern_height, &live_cells);
+    }
+
+    /// Load a pattern encoded in the simpler Life 1.06 format: a
+    /// `#Life 1.06` header followed by one `x y` integer coordinate pair
+    /// per live cell.
+    pub fn load_life_106(&mut self, life_106: &str) {
+        let mut live_cells = Vec::new();
+        let mut min_x = i64::MAX;
+        let mut min_y = i64::MAX;
+        let mut max_x = i64::MIN;
+        let mut max_y = i64::MIN;
+
+        for line in life_106.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let x: i64 = match parts.next().and_then(|v| v.parse().ok()) {
+                Some(x) => x,
+                None => continue,
+            };
+            let y: i64 = match parts.next().and_then(|v| v.parse().ok()) {
+                Some(y) => y,
+                None => continue,
+            };
+
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+            live_cells.push((x, y));
+        }
+
+        if live_cells.is_empty() {
+            return;
+        }
+
+        let pattern_width = (max_x - min_x + 1) as u32;
+        let pattern_height = (max_y - min_y + 1) as u32;
+        let live_cells: Vec<(u32, u32)> = live_cells
+            .into_iter()
+            .map(|(x, y)| ((x - min_x) as u32, (y - min_y) as u32))
+            .collect();
+
+        self.blit_centered(pattern_width, pattern_height, &live_cells);
+    }
+
+    /// Encode the current universe as an RLE pattern string, e.g. for
+    /// saving a user's creation to disk.
+    pub fn to_rle(&self) -> String {
+        let mut out = format!("x = {}, y = {}\n", self.width, self.height);
+        let mut line = String::new();
+
+        for row in 0..self.height {
+            let mut run_char: Option<Cell> = None;
+            let mut run_len = 0u32;
+
+            for col in 0..self.width {
+                let cell = self.cells[self.get_index(row, col)];
+                match run_char {
+                    Some(c) if c == cell => run_len += 1,
+                    Some(c) => {
+                        push_rle_run(&mut line, run_len, c);
+                        run_char = Some(cell);
+                        run_len = 1;
+                    }
+                    None => {
+                        run_char = Some(cell);
+                        run_len = 1;
+                    }
+                }
+            }
+            if let Some(c) = run_char {
+                // Trailing dead cells at the end of a row carry no
+                // information and are dropped, matching the convention
+                // used by LifeWiki-authored RLE files.
+                if c == Cell::Alive {
+                    push_rle_run(&mut line, run_len, c);
+                }
+            }
+
+            line.push('$');
+        }
+
+        out.push_str(&line);
+        out.push('!');
+        out
+    }
+}
+
+/// Built-in catalog of stampable patterns: name paired with the relative
+/// live-cell offsets `(dx, dy)` from the pattern's top-left corner.
+pub const STAMP_CATALOG: &[(&str, &[(i32, i32)])] = &[
+    ("Glider", &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]),
+    (
+        "LWSS",
+        &[
+            (1, 0), (4, 0),
+            (0, 1),
+            (0, 2), (4, 2),
+            (0, 3), (1, 3), (2, 3), (3, 3),
+        ],
+    ),
+    (
+        "Gosper glider gun",
+        &[
+            (24, 0),
+            (22, 1), (24, 1),
+            (12, 2), (13, 2), (20, 2), (21, 2), (34, 2), (35, 2),
+            (11, 3), (15, 3), (20, 3), (21, 3), (34, 3), (35, 3),
+            (0, 4), (1, 4), (10, 4), (16, 4), (20, 4), (21, 4),
+            (0, 5), (1, 5), (10, 5), (14, 5), (16, 5), (17, 5), (22, 5), (24, 5),
+            (10, 6), (16, 6), (24, 6),
+            (11, 7), (15, 7),
+            (12, 8), (13, 8),
+        ],
+    ),
+    (
+        "Pulsar",
+        &[
+            (2, 0), (3, 0), (4, 0), (8, 0), (9, 0), (10, 0),
+            (0, 2), (5, 2), (7, 2), (12, 2),
+            (0, 3), (5, 3), (7, 3), (12, 3),
+            (0, 4), (5, 4), (7, 4), (12, 4),
+            (2, 5), (3, 5), (4, 5), (8, 5), (9, 5), (10, 5),
+            (2, 7), (3, 7), (4, 7), (8, 7), (9, 7), (10, 7),
+            (0, 8), (5, 8), (7, 8), (12, 8),
+            (0, 9), (5, 9), (7, 9), (12, 9),
+            (0, 10), (5, 10), (7, 10), (12, 10),
+            (2, 12), (3, 12), (4, 12), (8, 12), (9, 12), (10, 12),
+        ],
+    ),
+];
+
+fn push_rle_run(out: &mut String, run_len: u32, cell: Cell) {
+    let tag = match cell {
+        Cell::Alive => 'o',
+        Cell::Dead => 'b',
+    };
+    if run_len > 1 {
+        out.push_str(&run_len.to_string());
+    }
+    out.push(tag);
+}
+
+/// Walk the integer grid from `(x0, y0)` to `(x1, y1)` using Bresenham's line
+/// algorithm, calling `plot` for every point on the segment (endpoints
+/// included).
+///
+/// Used to paint every cell a fast mouse drag passes over, instead of only
+/// the points that happened to land in a `MouseMotion` event.
+pub fn bresenham_line<F: FnMut(i32, i32)>(x0: i32, y0: i32, x1: i32, y1: i32, mut plot: F) {
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+    let mut err = dx + dy;
+
+    let mut x = x0;
+    let mut y = y0;
+
+    loop {
+        plot(x, y);
+
+        if x == x1 && y == y1 {
+            break;
+        }
+
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_alive(universe: &mut Universe, row: u32, col: u32) {
+        let index = universe.get_index(row, col);
+        universe.cells[index] = Cell::Alive;
+    }
+
+    fn alive_cells(universe: &Universe) -> Vec<(u32, u32)> {
+        let mut cells = Vec::new();
+        for row in 0..universe.height {
+            for col in 0..universe.width {
+                if universe.cells[universe.get_index(row, col)] == Cell::Alive {
+                    cells.push((col, row));
+                }
+            }
+        }
+        cells
+    }
+
+    #[test]
+    fn rule_parse_reads_birth_and_survival_digits() {
+        let rule = Rule::parse("B3/S23");
+        assert!(rule.is_born(3));
+        assert!(!rule.is_born(2));
+        assert!(rule.survives(2));
+        assert!(rule.survives(3));
+        assert!(!rule.survives(4));
+    }
+
+    #[test]
+    fn to_rle_round_trips_through_load_rle() {
+        let mut universe = Universe::new(4, 4);
+        set_alive(&mut universe, 0, 1);
+        set_alive(&mut universe, 1, 2);
+        set_alive(&mut universe, 2, 0);
+        set_alive(&mut universe, 2, 1);
+        set_alive(&mut universe, 2, 2);
+
+        let before = alive_cells(&universe);
+        let rle = universe.to_rle();
+
+        let mut reloaded = Universe::new(4, 4);
+        reloaded.load_rle(&rle);
+        let mut after = alive_cells(&reloaded);
+        let mut before = before;
+        before.sort();
+        after.sort();
+
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn load_rle_caps_an_oversized_run_instead_of_overflowing() {
+        let mut universe = Universe::new(4, 4);
+        // A run-length digit string far longer than `MAX_RUN_LENGTH` would
+        // overflow a naive `u32` accumulation; this should saturate instead
+        // of panicking.
+        let digits: String = "9".repeat(20);
+        let rle = format!("x = 4, y = 4\n{digits}o!");
+
+        universe.load_rle(&rle);
+    }
+
+    #[test]
+    fn bresenham_line_visits_both_endpoints() {
+        let mut points = Vec::new();
+        bresenham_line(0, 0, 3, 1, |x, y| points.push((x, y)));
+
+        assert_eq!(points.first(), Some(&(0, 0)));
+        assert_eq!(points.last(), Some(&(3, 1)));
+    }
+}
@@ -0,0 +1,155 @@
+use pixels::{Pixels, SurfaceTexture};
+use wasm_bindgen::prelude::*;
+use winit::dpi::LogicalSize;
+use winit::event::{ElementState, Event, MouseScrollDelta, VirtualKeyCode, WindowEvent};
+use winit::event_loop::{ControlFlow, EventLoop};
+use winit::platform::web::WindowExtWebSys;
+use winit::window::WindowBuilder;
+
+use crate::app_state::{AppState, GameOfLifeState};
+use crate::backend::{Backend, InputEvent, Key, MouseButton};
+use crate::pixels_backend::PixelsBackend;
+
+const ROWS: u32 = 64;
+const COLS: u32 = 115;
+const CELL_PX: u32 = 8;
+
+/// Browser entry point: drives the same `GameOfLifeState` the native build
+/// uses, via `winit`'s web event loop and a `pixels` framebuffer instead of
+/// an SDL2 canvas, so mouse painting, stamps, rule cycling and randomize
+/// work in the browser too. The RLE load/save keys read and write
+/// `pattern.rle` through `std::fs`, which has no file system on `wasm32`,
+/// so they do nothing here.
+#[wasm_bindgen(start)]
+pub fn start() -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let event_loop = EventLoop::new();
+    let size = LogicalSize::new(COLS * CELL_PX, ROWS * CELL_PX);
+    let window = WindowBuilder::new()
+        .with_inner_size(size)
+        .with_title("Game of Life")
+        .build(&event_loop)
+        .map_err(|e| JsValue::from_str(&format!("failed to build window: {:?}", e)))?;
+
+    web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|doc| doc.body())
+        .and_then(|body| body.append_child(&window.canvas()).ok())
+        .expect("couldn't append canvas to document body");
+
+    let physical_size = window.inner_size();
+    let surface_texture = SurfaceTexture::new(physical_size.width, physical_size.height, &window);
+    let pixels = Pixels::new(physical_size.width, physical_size.height, surface_texture)
+        .map_err(|e| JsValue::from_str(&format!("failed to create pixel buffer: {:?}", e)))?;
+
+    let mut state = GameOfLifeState::new(ROWS, COLS);
+    state.universe_mut().run();
+    let mut backend = PixelsBackend::new(pixels, physical_size.width);
+    let mut frame_timer = std::time::Instant::now();
+    // `WindowEvent::MouseInput` carries a button but not a position, so the
+    // most recent `CursorMoved` has to be tracked separately to fill in the
+    // x/y that `InputEvent::MouseButtonDown` needs.
+    let mut cursor_pos = (0i32, 0i32);
+
+    event_loop.run(move |event, _, control_flow| {
+        *control_flow = ControlFlow::Poll;
+
+        match event {
+            Event::WindowEvent {event: WindowEvent::CloseRequested, ..} => {
+                backend.push_event(InputEvent::Quit);
+            }
+            Event::WindowEvent {event: window_event, ..} => {
+                if let Some(input_event) = translate_window_event(&window_event, &mut cursor_pos) {
+                    backend.push_event(input_event);
+                }
+            }
+            Event::MainEventsCleared => {
+                for input_event in backend.poll_events() {
+                    if matches!(input_event, InputEvent::Quit) {
+                        *control_flow = ControlFlow::Exit;
+                        return;
+                    }
+                    state.handle_event(&input_event);
+                }
+
+                let dt_ms = frame_timer.elapsed().as_millis();
+                frame_timer = std::time::Instant::now();
+                state.update(dt_ms);
+
+                backend.clear();
+                state.render(&mut backend);
+                backend.present();
+            }
+            _ => {}
+        }
+    });
+}
+
+/// Translates the subset of `WindowEvent` the simulation cares about into
+/// `InputEvent`, mirroring `sdl2_backend::translate_event`. `cursor_pos` is
+/// updated on every `CursorMoved` and reused to stamp `MouseButtonDown`,
+/// since winit reports button presses without a position of their own.
+fn translate_window_event(event: &WindowEvent, cursor_pos: &mut (i32, i32)) -> Option<InputEvent> {
+    match event {
+        WindowEvent::CursorMoved { position, .. } => {
+            *cursor_pos = (position.x as i32, position.y as i32);
+            Some(InputEvent::MouseMotion { x: cursor_pos.0, y: cursor_pos.1 })
+        }
+        WindowEvent::MouseInput { state, button, .. } => {
+            let button = translate_mouse_button(*button)?;
+            match state {
+                ElementState::Pressed => Some(InputEvent::MouseButtonDown {
+                    button,
+                    x: cursor_pos.0,
+                    y: cursor_pos.1,
+                }),
+                ElementState::Released => Some(InputEvent::MouseButtonUp { button }),
+            }
+        }
+        WindowEvent::MouseWheel { delta, .. } => {
+            let y = match *delta {
+                MouseScrollDelta::LineDelta(_, y) => y,
+                MouseScrollDelta::PixelDelta(position) => position.y as f32,
+            };
+            match y {
+                y if y > 0.0 => Some(InputEvent::MouseWheel { y: 1 }),
+                y if y < 0.0 => Some(InputEvent::MouseWheel { y: -1 }),
+                _ => None,
+            }
+        }
+        WindowEvent::KeyboardInput {
+            input: winit::event::KeyboardInput { state: ElementState::Pressed, virtual_keycode: Some(keycode), .. },
+            ..
+        } => translate_keycode(*keycode).map(InputEvent::KeyDown),
+        _ => None,
+    }
+}
+
+fn translate_keycode(keycode: VirtualKeyCode) -> Option<Key> {
+    match keycode {
+        VirtualKeyCode::Space => Some(Key::Space),
+        VirtualKeyCode::Right => Some(Key::Right),
+        VirtualKeyCode::Escape => Some(Key::Escape),
+        VirtualKeyCode::R => Some(Key::R),
+        VirtualKeyCode::L => Some(Key::L),
+        VirtualKeyCode::Key1 => Some(Key::Num1),
+        VirtualKeyCode::Key2 => Some(Key::Num2),
+        VirtualKeyCode::Key3 => Some(Key::Num3),
+        VirtualKeyCode::O => Some(Key::O),
+        VirtualKeyCode::S => Some(Key::S),
+        VirtualKeyCode::Tab => Some(Key::Tab),
+        VirtualKeyCode::Comma => Some(Key::Comma),
+        VirtualKeyCode::Period => Some(Key::Period),
+        _ => None,
+    }
+}
+
+fn translate_mouse_button(button: winit::event::MouseButton) -> Option<MouseButton> {
+    match button {
+        winit::event::MouseButton::Left => Some(MouseButton::Left),
+        winit::event::MouseButton::Right => Some(MouseButton::Right),
+        winit::event::MouseButton::Middle => Some(MouseButton::Middle),
+        winit::event::MouseButton::Other(_) => None,
+    }
+}